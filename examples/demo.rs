@@ -1,13 +1,15 @@
 use bevy::{asset::LoadState, prelude::*};
-use bevy_midi_graph::{MidiGraphAsset, MidiGraphAudioContext, MidiGraphPlugin};
+use bevy_midi_graph::{
+    dispatch_midi_graph_triggers, update_spatial_emitters, ActiveTriggerSensors, MidiGraphAsset,
+    MidiGraphAudioContext, MidiGraphListener, MidiGraphPlugin, MidiGraphTriggerAsset,
+    MidiGraphTriggerAssetLoader, MidiGraphTriggers, SpatialEmitter, TriggerSensor,
+};
 use bevy_rapier3d::{control::KinematicCharacterController, prelude::*};
-use midi_graph::{EventChannel, NodeControlEvent, NodeEvent};
 
 const PLAYER_VELOCITY: f32 = 3.0;
 
-const MIDI_NODE_ID: u64 = 101;
-const DEFAULT_ANCHOR: u32 = 0;
-const ENTER_TENSION_ANCHOR: u32 = 1;
+const AMBIENT_NODE_ID: u64 = 102;
+const TENSION_SENSOR: &str = "tension";
 
 #[derive(Component)]
 struct Player;
@@ -15,15 +17,31 @@ struct Player;
 #[derive(Resource, Default)]
 struct GraphAssetLoading(Handle<MidiGraphAsset>);
 
+#[derive(Resource, Default)]
+struct TriggersLoading(Handle<MidiGraphTriggerAsset>);
+
 pub fn main() {
     App::new()
         .insert_resource(GraphAssetLoading::default())
+        .insert_resource(TriggersLoading::default())
+        .init_resource::<MidiGraphTriggers>()
+        .init_resource::<ActiveTriggerSensors>()
+        .init_asset::<MidiGraphTriggerAsset>()
+        .init_asset_loader::<MidiGraphTriggerAssetLoader>()
         .add_plugins(DefaultPlugins)
         .add_plugins(RapierPhysicsPlugin::<NoUserData>::default())
         .add_plugins(MidiGraphPlugin)
         .add_systems(Startup, setup)
-        .add_systems(Update, (move_character, check_graph_ready))
-        .add_systems(PostUpdate, check_intersections)
+        .add_systems(
+            Update,
+            (
+                move_character,
+                check_graph_ready,
+                check_triggers_ready,
+                update_spatial_emitters,
+            ),
+        )
+        .add_systems(PostUpdate, dispatch_midi_graph_triggers)
         .run();
 }
 
@@ -32,12 +50,16 @@ fn setup(
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     mut graph_asset: ResMut<GraphAssetLoading>,
+    mut triggers_loading: ResMut<TriggersLoading>,
     asset_server: Res<AssetServer>,
 ) {
-    commands.spawn(Camera3dBundle {
-        transform: Transform::from_xyz(0.0, 1.0, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
-        ..default()
-    });
+    commands.spawn((
+        MidiGraphListener,
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 1.0, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        },
+    ));
     commands.spawn(PbrBundle {
         transform: Transform::from_translation(Vec3::ZERO),
         mesh: meshes.add(Plane3d::new(Vec3::Y, Vec2::splat(10.0))),
@@ -69,8 +91,10 @@ fn setup(
         },
     ));
     commands.spawn((
+        TriggerSensor(TENSION_SENSOR.to_owned()),
         Sensor,
         Collider::cuboid(3.0, 3.0, 3.0),
+        ActiveEvents::COLLISION_EVENTS,
         PbrBundle {
             mesh: meshes.add(Cuboid::new(6.0, 6.0, 6.0)),
             material: materials.add(StandardMaterial {
@@ -81,7 +105,34 @@ fn setup(
             ..default()
         },
     ));
+    commands.spawn((
+        SpatialEmitter::new(AMBIENT_NODE_ID, 2.0, 1.0, 20.0),
+        Transform::from_xyz(4.0, 1.0, -6.0),
+    ));
     graph_asset.0 = asset_server.load("demo/graph.ron");
+    triggers_loading.0 = asset_server.load("demo/demo.triggers.ron");
+}
+
+fn check_triggers_ready(
+    server: Res<AssetServer>,
+    loading: Res<TriggersLoading>,
+    assets: Res<Assets<MidiGraphTriggerAsset>>,
+    mut triggers: ResMut<MidiGraphTriggers>,
+    mut triggers_did_load: Local<bool>,
+) {
+    if *triggers_did_load {
+        return;
+    }
+    let load_state = server.get_load_state(loading.0.id()).unwrap();
+    match load_state {
+        LoadState::Failed(e) => panic!("{}", e),
+        LoadState::Loaded => {
+            *triggers_did_load = true;
+            let asset = assets.get(&loading.0).unwrap();
+            *triggers = asset.triggers.clone();
+        }
+        _ => {}
+    }
 }
 
 fn check_graph_ready(
@@ -143,36 +194,3 @@ fn move_character(
         quit_signal.send(AppExit::Success);
     }
 }
-
-fn check_intersections(
-    graph: Res<GraphAssetLoading>,
-    rapier_context: Res<RapierContext>,
-    mut audio_context: ResMut<MidiGraphAudioContext>,
-    player_query: Query<Entity, With<Player>>,
-    sensor_query: Query<Entity, With<Sensor>>,
-    mut graphs: ResMut<Assets<MidiGraphAsset>>,
-    mut current_anchor: Local<u32>,
-) {
-    let player_entity = player_query.get_single().unwrap();
-    let sensor_entity = sensor_query.get_single().unwrap();
-    let desired_track = match rapier_context.intersection_pair(player_entity, sensor_entity) {
-        Some(true) => ENTER_TENSION_ANCHOR,
-        _ => DEFAULT_ANCHOR,
-    };
-    if *current_anchor != desired_track {
-        *current_anchor = desired_track;
-        let graph_id = graph.0.id();
-        if let Some(graph) = graphs.get_mut(graph_id) {
-            let channel: &mut EventChannel = audio_context.event_channel.get();
-            let send = channel.try_send(NodeEvent::NodeControl {
-                node_id: MIDI_NODE_ID,
-                event: NodeControlEvent::SeekWhenIdeal {
-                    to_anchor: Some(desired_track),
-                },
-            });
-            if let Err(err) = send {
-                panic!("{:?}", err);
-            }
-        }
-    }
-}
\ No newline at end of file
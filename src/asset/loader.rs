@@ -1,9 +1,10 @@
 use crate::{LoopFileSource, MidiFileSource, OneShotFileSource, Sf2FileSource};
 use bevy::prelude::*;
 use midi_graph::{
-    util, AsyncEventReceiver, CombinerSource, Envelope, Error, EventChannel, Fader, FontSource,
-    GraphLoader, LfsrNoiseSource, LoopRange, MidiDataSource, MixerSource, Node, NoteRange,
-    SawtoothWaveSource, SoundFontBuilder, SoundSource, SquareWaveSource, TriangleWaveSource,
+    util, AsyncEventReceiver, BiquadFilterSource, CombinerSource, Envelope, Error, EventChannel,
+    Fader, FontSource, GraphLoader, LfoSource, LfsrNoiseSource, LoopRange, MidiDataSource,
+    MixerSource, Node, NoteRange, SawtoothWaveSource, SoundFontBuilder, SoundSource, SpatialSource,
+    SquareWaveSource, TriangleWaveSource,
 };
 
 pub struct GraphAssetLoader<'a> {
@@ -223,6 +224,56 @@ impl<'a> GraphLoader for GraphAssetLoader<'a> {
                 let source: Box<dyn Node + Send + 'static> = Box::new(source);
                 (channels, source)
             }
+            SoundSource::Spatial {
+                node_id,
+                source,
+                reference_distance,
+                rolloff,
+                max_distance,
+            } => {
+                let (channels, source) = self.load_source_recursive(source)?;
+                let source = SpatialSource::new(
+                    *node_id,
+                    *reference_distance,
+                    *rolloff,
+                    *max_distance,
+                    source,
+                );
+                let source: Box<dyn Node + Send + 'static> = Box::new(source);
+                (channels, source)
+            }
+            SoundSource::Filter {
+                node_id,
+                source,
+                mode,
+                cutoff_hz,
+                resonance,
+            } => {
+                let (channels, source) = self.load_source_recursive(source)?;
+                let source =
+                    BiquadFilterSource::new(*node_id, *mode, *cutoff_hz, *resonance, source);
+                let source: Box<dyn Node + Send + 'static> = Box::new(source);
+                (channels, source)
+            }
+            SoundSource::Lfo {
+                node_id,
+                waveform,
+                frequency_hz,
+                depth,
+                target_node_id,
+                target_param,
+            } => {
+                let source = LfoSource::new(
+                    *node_id,
+                    *waveform,
+                    *frequency_hz,
+                    *depth,
+                    *target_node_id,
+                    *target_param,
+                );
+                let source: Box<dyn Node + Send + 'static> = Box::new(source);
+                (vec![], source)
+            }
         };
         Ok((event_channels, consumer))
     }
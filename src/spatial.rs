@@ -0,0 +1,76 @@
+use crate::MidiGraphAudioContext;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use midi_graph::{EventChannel, NodeControlEvent, NodeEvent};
+use std::f32::consts::PI;
+
+const EPSILON: f32 = 0.001;
+
+#[derive(Component)]
+pub struct MidiGraphListener;
+
+#[derive(Component)]
+pub struct SpatialEmitter {
+    node_id: u64,
+    reference_distance: f32,
+    rolloff: f32,
+    max_distance: f32,
+}
+
+impl SpatialEmitter {
+    pub fn new(node_id: u64, reference_distance: f32, rolloff: f32, max_distance: f32) -> Self {
+        Self {
+            node_id,
+            reference_distance,
+            rolloff,
+            max_distance: max_distance.max(reference_distance),
+        }
+    }
+}
+
+pub fn update_spatial_emitters(
+    mut audio_context: ResMut<MidiGraphAudioContext>,
+    listener_query: Query<&Transform, With<MidiGraphListener>>,
+    emitter_query: Query<(Entity, &Transform, &SpatialEmitter)>,
+    mut last_sent: Local<HashMap<Entity, (f32, f32)>>,
+) {
+    let Ok(listener) = listener_query.get_single() else {
+        return;
+    };
+    let listener_right = listener.right();
+
+    for (entity, transform, emitter) in emitter_query.iter() {
+        let relative = transform.translation - listener.translation;
+        let distance = relative.length();
+        let clamped_distance = distance.clamp(emitter.reference_distance, emitter.max_distance);
+        let gain = emitter.reference_distance
+            / (emitter.reference_distance
+                + emitter.rolloff * (clamped_distance - emitter.reference_distance));
+        let pan = Vec3::new(relative.x, 0.0, relative.z)
+            .normalize_or_zero()
+            .dot(*listener_right)
+            .clamp(-1.0, 1.0);
+
+        let (last_gain, last_pan) = last_sent
+            .get(&entity)
+            .copied()
+            .unwrap_or((f32::MIN, f32::MIN));
+        if (gain - last_gain).abs() < EPSILON && (pan - last_pan).abs() < EPSILON {
+            continue;
+        }
+        last_sent.insert(entity, (gain, pan));
+
+        let angle = (pan + 1.0) * PI / 4.0;
+        let left = angle.cos();
+        let right = angle.sin();
+
+        let channel: &mut EventChannel = audio_context.event_channel.get();
+        let send = channel.try_send(NodeEvent::NodeControl {
+            node_id: emitter.node_id,
+            event: NodeControlEvent::Spatial { gain, left, right },
+        });
+        if let Err(err) = send {
+            panic!("{:?}", err);
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use crate::MidiGraphAudioContext;
+use bevy::asset::{io::Reader, AssetLoader, LoadContext};
+use bevy::prelude::*;
+use bevy::utils::{BoxedFuture, HashMap, HashSet};
+use bevy_rapier3d::prelude::CollisionEvent;
+use midi_graph::{EventChannel, NodeEvent};
+use serde::Deserialize;
+
+#[derive(Component)]
+pub struct TriggerSensor(pub String);
+
+#[derive(Clone, Deserialize)]
+pub enum TriggerCause {
+    SensorEnter(String),
+    SensorExit(String),
+}
+
+#[derive(Clone, Deserialize)]
+pub struct MidiGraphTriggerRule {
+    pub when: TriggerCause,
+    pub send: Vec<NodeEvent>,
+}
+
+#[derive(Clone, Deserialize, Resource, Default)]
+pub struct MidiGraphTriggers {
+    pub rules: Vec<MidiGraphTriggerRule>,
+}
+
+#[derive(Asset, TypePath, Clone, Deserialize, Default)]
+pub struct MidiGraphTriggerAsset {
+    pub triggers: MidiGraphTriggers,
+}
+
+#[derive(Debug)]
+pub enum MidiGraphTriggerAssetError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+}
+
+impl std::fmt::Display for MidiGraphTriggerAssetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "failed to read trigger asset: {}", err),
+            Self::Ron(err) => write!(f, "failed to parse trigger asset: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for MidiGraphTriggerAssetError {}
+
+impl From<std::io::Error> for MidiGraphTriggerAssetError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<ron::error::SpannedError> for MidiGraphTriggerAssetError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        Self::Ron(err)
+    }
+}
+
+#[derive(Default)]
+pub struct MidiGraphTriggerAssetLoader;
+
+impl AssetLoader for MidiGraphTriggerAssetLoader {
+    type Asset = MidiGraphTriggerAsset;
+    type Settings = ();
+    type Error = MidiGraphTriggerAssetError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a Self::Settings,
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let asset = ron::de::from_bytes::<MidiGraphTriggerAsset>(&bytes)?;
+            Ok(asset)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["triggers.ron"]
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct ActiveTriggerSensors(HashMap<String, HashSet<Entity>>);
+
+pub fn dispatch_midi_graph_triggers(
+    triggers: Res<MidiGraphTriggers>,
+    mut active: ResMut<ActiveTriggerSensors>,
+    mut collision_events: EventReader<CollisionEvent>,
+    sensor_query: Query<&TriggerSensor>,
+    mut audio_context: ResMut<MidiGraphAudioContext>,
+) {
+    for event in collision_events.read() {
+        let (entity_a, entity_b, started) = match event {
+            CollisionEvent::Started(a, b, _) => (*a, *b, true),
+            CollisionEvent::Stopped(a, b, _) => (*a, *b, false),
+        };
+        for (sensor_entity, other_entity) in [(entity_a, entity_b), (entity_b, entity_a)] {
+            let Ok(sensor) = sensor_query.get(sensor_entity) else {
+                continue;
+            };
+            let overlapping = active.0.entry(sensor.0.clone()).or_default();
+            let was_active = !overlapping.is_empty();
+            if started {
+                overlapping.insert(other_entity);
+            } else {
+                overlapping.remove(&other_entity);
+            }
+            let is_active = !overlapping.is_empty();
+            if is_active == was_active {
+                continue;
+            }
+
+            for rule in triggers.rules.iter() {
+                let matches = match &rule.when {
+                    TriggerCause::SensorEnter(name) => is_active && *name == sensor.0,
+                    TriggerCause::SensorExit(name) => !is_active && *name == sensor.0,
+                };
+                if !matches {
+                    continue;
+                }
+                let channel: &mut EventChannel = audio_context.event_channel.get();
+                for node_event in rule.send.iter().cloned() {
+                    if let Err(err) = channel.try_send(node_event) {
+                        panic!("{:?}", err);
+                    }
+                }
+            }
+        }
+    }
+}